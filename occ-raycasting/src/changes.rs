@@ -0,0 +1,233 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use crate::{Error, Visibility};
+
+/// The thresholds used to decide when an object changes its visibility state.
+///
+/// An object becomes visible once its visibility exceeds `show` and only becomes hidden again
+/// once its visibility drops to `hide` or below. Choosing `hide` smaller than `show` avoids
+/// objects flickering between the two states when their visibility hovers around a single value.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChangeThresholds {
+    /// Visibility that must be exceeded for a hidden object to become visible.
+    pub show: f32,
+
+    /// Visibility at or below which a visible object becomes hidden.
+    pub hide: f32,
+
+    /// Minimal difference to the last reported visibility for a visible object to be reported as
+    /// changed.
+    pub coverage_delta: f32,
+}
+
+impl Default for ChangeThresholds {
+    /// Every object with a non-zero visibility is visible and every change is reported.
+    fn default() -> Self {
+        Self {
+            show: 0.0,
+            hide: 0.0,
+            coverage_delta: 0.0,
+        }
+    }
+}
+
+/// The changes between two consecutive visibility results. All object ids are sorted ascending.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VisibilityChanges {
+    /// The objects that have been hidden before and are visible now.
+    pub became_visible: Vec<u32>,
+
+    /// The objects that have been visible before and are hidden now.
+    pub became_hidden: Vec<u32>,
+
+    /// The objects that stayed visible, but whose visibility changed by more than the coverage
+    /// delta since it was last reported.
+    pub coverage_changed: Vec<u32>,
+}
+
+impl VisibilityChanges {
+    /// Returns true if there are no changes at all.
+    pub fn is_empty(&self) -> bool {
+        self.became_visible.is_empty()
+            && self.became_hidden.is_empty()
+            && self.coverage_changed.is_empty()
+    }
+}
+
+/// Tracks the set of visible objects over consecutive visibility results and reports the
+/// changes between them.
+#[derive(Debug, Clone, Default)]
+pub struct VisibilityTracker {
+    thresholds: ChangeThresholds,
+
+    /// The currently visible objects with their last reported visibility.
+    visible: HashMap<u32, f32>,
+}
+
+impl VisibilityTracker {
+    /// Creates a new tracker with no visible objects.
+    ///
+    /// # Arguments
+    /// * `thresholds` - The thresholds for changing the visibility state of an object.
+    ///
+    /// # Panics
+    /// Panics if the hide threshold is greater than the show threshold or one of them is NaN.
+    /// Use [`VisibilityTracker::try_new`] for a fallible variant, e.g., for configured thresholds.
+    pub fn new(thresholds: ChangeThresholds) -> Self {
+        Self::try_new(thresholds).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Creates a new tracker with no visible objects. Returns an error if the hide threshold is
+    /// greater than the show threshold or one of them is NaN.
+    ///
+    /// # Arguments
+    /// * `thresholds` - The thresholds for changing the visibility state of an object.
+    pub fn try_new(thresholds: ChangeThresholds) -> Result<Self, Error> {
+        let ordered = matches!(
+            thresholds.hide.partial_cmp(&thresholds.show),
+            Some(Ordering::Less | Ordering::Equal)
+        );
+        if !ordered {
+            return Err(Error::InvalidThresholds(thresholds.show, thresholds.hide));
+        }
+
+        Ok(Self {
+            thresholds,
+            visible: HashMap::new(),
+        })
+    }
+
+    /// Returns true if the given object is currently considered visible.
+    ///
+    /// # Arguments
+    /// * `object_id` - The id of the object to check.
+    pub fn is_visible(&self, object_id: u32) -> bool {
+        self.visible.contains_key(&object_id)
+    }
+
+    /// Updates the tracker with the next visibility result and returns the changes compared to
+    /// the previous state. Objects missing in the result are treated as having zero visibility.
+    ///
+    /// # Arguments
+    /// * `visibility` - The next visibility result.
+    pub fn update(&mut self, visibility: &Visibility) -> VisibilityChanges {
+        let thresholds = self.thresholds;
+        let current: HashMap<u32, f32> = visibility.iter_sorted().collect();
+        let mut changes = VisibilityChanges::default();
+
+        self.visible.retain(|object_id, reported| {
+            let value = current.get(object_id).copied().unwrap_or(0.0);
+            if value <= thresholds.hide {
+                changes.became_hidden.push(*object_id);
+                return false;
+            }
+
+            if (value - *reported).abs() > thresholds.coverage_delta {
+                changes.coverage_changed.push(*object_id);
+                *reported = value;
+            }
+
+            true
+        });
+
+        for (object_id, value) in visibility.visible_above(thresholds.show) {
+            if !self.visible.contains_key(object_id) {
+                changes.became_visible.push(*object_id);
+                self.visible.insert(*object_id, *value);
+            }
+        }
+
+        changes.became_visible.sort_unstable();
+        changes.became_hidden.sort_unstable();
+        changes.coverage_changed.sort_unstable();
+
+        changes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_thresholds() {
+        let mut tracker = VisibilityTracker::default();
+
+        let changes = tracker.update(&Visibility::new(vec![(0, 0.5), (1, 0.2), (2, 0.0)]));
+        assert_eq!(changes.became_visible, vec![0, 1]);
+        assert!(changes.became_hidden.is_empty());
+        assert!(tracker.is_visible(0));
+        assert!(!tracker.is_visible(2));
+
+        let changes = tracker.update(&Visibility::new(vec![(0, 0.4), (2, 0.1)]));
+        assert_eq!(changes.became_visible, vec![2]);
+        assert_eq!(changes.became_hidden, vec![1]);
+        assert_eq!(changes.coverage_changed, vec![0]);
+
+        let changes = tracker.update(&Visibility::new(vec![(0, 0.4), (2, 0.1)]));
+        assert!(changes.is_empty());
+    }
+
+    #[test]
+    fn test_hysteresis() {
+        let mut tracker = VisibilityTracker::new(ChangeThresholds {
+            show: 0.1,
+            hide: 0.05,
+            coverage_delta: 0.1,
+        });
+
+        let changes = tracker.update(&Visibility::new(vec![(0, 0.08), (1, 0.12)]));
+        assert_eq!(changes.became_visible, vec![1]);
+
+        // object 1 stays visible between the thresholds and the small change is not reported
+        let changes = tracker.update(&Visibility::new(vec![(0, 0.09), (1, 0.07)]));
+        assert!(changes.is_empty());
+
+        let changes = tracker.update(&Visibility::new(vec![(0, 0.3), (1, 0.05)]));
+        assert_eq!(changes.became_visible, vec![0]);
+        assert_eq!(changes.became_hidden, vec![1]);
+        assert!(changes.coverage_changed.is_empty());
+    }
+
+    #[test]
+    fn test_try_new() {
+        let thresholds = ChangeThresholds {
+            show: 0.1,
+            hide: 0.5,
+            coverage_delta: 0.0,
+        };
+        assert_eq!(
+            VisibilityTracker::try_new(thresholds).unwrap_err(),
+            Error::InvalidThresholds(0.1, 0.5)
+        );
+
+        let thresholds = ChangeThresholds {
+            show: f32::NAN,
+            ..Default::default()
+        };
+        assert!(VisibilityTracker::try_new(thresholds).is_err());
+
+        assert!(VisibilityTracker::try_new(ChangeThresholds::default()).is_ok());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_deserialized_thresholds() {
+        let json = r#"{"show":0.1,"hide":0.5,"coverage_delta":0.0}"#;
+        let thresholds: ChangeThresholds = serde_json::from_str(json).unwrap();
+        assert!(VisibilityTracker::try_new(thresholds).is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_invalid_thresholds() {
+        VisibilityTracker::new(ChangeThresholds {
+            show: 0.1,
+            hide: 0.2,
+            coverage_delta: 0.0,
+        });
+    }
+}
@@ -0,0 +1,55 @@
+use std::fmt;
+
+/// The errors of the occlusion library.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error {
+    /// The visibility of the given object is NaN or infinite.
+    NonFiniteVisibility(u32),
+
+    /// The visibility of the given object is outside of [0, 1].
+    VisibilityOutOfRange(u32, f32),
+
+    /// The given object occurs more than once in a visibility result.
+    DuplicateObject(u32),
+
+    /// The total number of pixels of a frame is zero.
+    NoPixels,
+
+    /// The visible pixel count of the given object exceeds the number of pixels of the frame.
+    PixelCountOutOfRange(u32, u32),
+
+    /// The given show and hide thresholds are invalid, as the hide threshold is greater than the
+    /// show threshold or one of them is NaN.
+    InvalidThresholds(f32, f32),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NonFiniteVisibility(object_id) => {
+                write!(f, "Visibility of object {} is not finite", object_id)
+            }
+            Error::VisibilityOutOfRange(object_id, visibility) => write!(
+                f,
+                "Visibility {} of object {} is outside of [0, 1]",
+                visibility, object_id
+            ),
+            Error::DuplicateObject(object_id) => {
+                write!(f, "Object {} occurs more than once", object_id)
+            }
+            Error::NoPixels => write!(f, "Number of pixels must be greater than zero"),
+            Error::PixelCountOutOfRange(object_id, count) => write!(
+                f,
+                "Pixel count {} of object {} exceeds the number of pixels",
+                count, object_id
+            ),
+            Error::InvalidThresholds(show, hide) => write!(
+                f,
+                "Hide threshold {} must not be greater than the show threshold {}",
+                hide, show
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
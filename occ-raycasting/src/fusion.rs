@@ -0,0 +1,168 @@
+use std::collections::HashMap;
+
+use crate::Visibility;
+
+/// The way the visibility of an object is combined over multiple views.
+/// Objects missing in a view are treated as having zero visibility in that view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FusionMode {
+    /// The maximal visibility over all views.
+    #[default]
+    Max,
+
+    /// The average visibility over all views.
+    Mean,
+
+    /// The union of the coverages, i.e., `1 - (1 - v_1) * ... * (1 - v_n)`, which treats the
+    /// coverages of the different views as independent.
+    Union,
+}
+
+/// Accumulates the visibility of the objects over a set of views.
+#[derive(Debug, Clone, Default)]
+pub struct VisibilityAccumulator {
+    mode: FusionMode,
+    num_views: usize,
+
+    /// The accumulated value per object. Depending on the mode, this is the maximum, the sum or
+    /// the product of the hidden fractions.
+    values: HashMap<u32, f64>,
+}
+
+impl VisibilityAccumulator {
+    /// Creates a new accumulator without any views.
+    ///
+    /// # Arguments
+    /// * `mode` - The way the visibility of the views is combined.
+    pub fn new(mode: FusionMode) -> Self {
+        Self {
+            mode,
+            num_views: 0,
+            values: HashMap::new(),
+        }
+    }
+
+    /// Creates the fused visibility for the given views.
+    ///
+    /// # Arguments
+    /// * `mode` - The way the visibility of the views is combined.
+    /// * `views` - The visibility results of the views.
+    pub fn fuse<'a, I>(mode: FusionMode, views: I) -> Visibility
+    where
+        I: IntoIterator<Item = &'a Visibility>,
+    {
+        let mut accumulator = Self::new(mode);
+        for visibility in views {
+            accumulator.add(visibility);
+        }
+
+        accumulator.result()
+    }
+
+    /// Returns the number of views added so far.
+    #[inline]
+    pub fn num_views(&self) -> usize {
+        self.num_views
+    }
+
+    /// Adds the visibility result of the next view.
+    ///
+    /// # Arguments
+    /// * `visibility` - The visibility result of the view.
+    pub fn add(&mut self, visibility: &Visibility) {
+        self.num_views += 1;
+
+        for (object_id, value) in visibility.iter_sorted() {
+            let value = value as f64;
+            match self.mode {
+                FusionMode::Max => {
+                    let max = self.values.entry(object_id).or_insert(0.0);
+                    *max = max.max(value);
+                }
+                FusionMode::Mean => *self.values.entry(object_id).or_insert(0.0) += value,
+                FusionMode::Union => *self.values.entry(object_id).or_insert(1.0) *= 1.0 - value,
+            }
+        }
+    }
+
+    /// Returns the fused visibility of all objects seen in any of the views.
+    ///
+    /// As the visibility of every view is within [0, 1], so is the fused visibility for all modes.
+    pub fn result(&self) -> Visibility {
+        let entries = self
+            .values
+            .iter()
+            .map(|(object_id, value)| {
+                let visibility = match self.mode {
+                    FusionMode::Max => *value,
+                    FusionMode::Mean => *value / self.num_views as f64,
+                    FusionMode::Union => 1.0 - *value,
+                };
+
+                // guards against rounding errors pushing the value out of [0, 1]
+                (*object_id, visibility.clamp(0.0, 1.0) as f32)
+            })
+            .collect();
+
+        Visibility::new(entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn views() -> Vec<Visibility> {
+        vec![
+            Visibility::new(vec![(0, 0.5), (1, 0.25)]),
+            Visibility::new(vec![(0, 0.5), (2, 0.5)]),
+        ]
+    }
+
+    #[test]
+    fn test_max() {
+        let fused = VisibilityAccumulator::fuse(FusionMode::Max, &views());
+        assert_eq!(fused.as_slice(), &[(0, 0.5), (2, 0.5), (1, 0.25)]);
+    }
+
+    #[test]
+    fn test_mean() {
+        let fused = VisibilityAccumulator::fuse(FusionMode::Mean, &views());
+        assert_eq!(fused.as_slice(), &[(0, 0.5), (2, 0.25), (1, 0.125)]);
+    }
+
+    #[test]
+    fn test_union() {
+        let fused = VisibilityAccumulator::fuse(FusionMode::Union, &views());
+        assert_eq!(fused.as_slice(), &[(0, 0.75), (2, 0.5), (1, 0.25)]);
+    }
+
+    #[test]
+    fn test_full_coverage() {
+        let full = Visibility::new(vec![(0, 1.0), (1, 0.999)]);
+        let views = vec![full; 100];
+
+        for mode in [FusionMode::Max, FusionMode::Mean, FusionMode::Union] {
+            let fused = VisibilityAccumulator::fuse(mode, &views);
+            assert_eq!(fused.get(0), Some(1.0));
+            assert!(fused.get(1).unwrap() <= 1.0);
+        }
+
+        // out-of-range values cannot enter the fusion in the first place
+        assert!(Visibility::try_new(vec![(0, 3.0e38)]).is_err());
+    }
+
+    #[test]
+    fn test_incremental() {
+        let mut accumulator = VisibilityAccumulator::new(FusionMode::Mean);
+        assert!(accumulator.result().is_empty());
+
+        for visibility in views().iter() {
+            accumulator.add(visibility);
+        }
+
+        assert_eq!(accumulator.num_views(), 2);
+        assert_eq!(accumulator.result().get(1), Some(0.125));
+    }
+}
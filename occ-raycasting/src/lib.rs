@@ -1,3 +1,16 @@
+mod changes;
+mod error;
+mod fusion;
+pub mod metrics;
+mod report;
+mod visibility;
+
+pub use changes::{ChangeThresholds, VisibilityChanges, VisibilityTracker};
+pub use error::Error;
+pub use fusion::{FusionMode, VisibilityAccumulator};
+pub use report::VisibilityReport;
+pub use visibility::Visibility;
+
 pub fn add(left: u64, right: u64) -> u64 {
     left + right
 }
@@ -0,0 +1,228 @@
+//! Accuracy metrics for comparing a visibility result against a reference result.
+
+use std::collections::{BTreeSet, HashMap};
+
+use crate::Visibility;
+
+/// Precision, recall and F1 score of the set of visible objects.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SetMetrics {
+    /// Fraction of the objects visible in the result that are visible in the reference as well.
+    pub precision: f32,
+
+    /// Fraction of the objects visible in the reference that are visible in the result as well.
+    pub recall: f32,
+
+    /// The harmonic mean of precision and recall.
+    pub f1: f32,
+}
+
+/// Computes precision, recall and F1 score of the objects considered visible in `result` compared
+/// to the objects visible in `reference`. An object is visible if its visibility is strictly
+/// greater than the given threshold.
+///
+/// If `result` has no visible objects, the precision is 1. If `reference` has no visible objects,
+/// the recall is 1.
+///
+/// # Arguments
+/// * `reference` - The reference visibility, e.g., from a ground truth setup.
+/// * `result` - The visibility result to evaluate.
+/// * `threshold` - The visibility threshold for an object to count as visible.
+pub fn set_metrics(reference: &Visibility, result: &Visibility, threshold: f32) -> SetMetrics {
+    let expected: BTreeSet<u32> = reference
+        .visible_above(threshold)
+        .iter()
+        .map(|(object_id, _)| *object_id)
+        .collect();
+    let actual: BTreeSet<u32> = result
+        .visible_above(threshold)
+        .iter()
+        .map(|(object_id, _)| *object_id)
+        .collect();
+
+    let true_positives = expected.intersection(&actual).count() as f32;
+    let precision = if actual.is_empty() {
+        1.0
+    } else {
+        true_positives / actual.len() as f32
+    };
+    let recall = if expected.is_empty() {
+        1.0
+    } else {
+        true_positives / expected.len() as f32
+    };
+    let f1 = if precision + recall > 0.0 {
+        2.0 * precision * recall / (precision + recall)
+    } else {
+        0.0
+    };
+
+    SetMetrics {
+        precision,
+        recall,
+        f1,
+    }
+}
+
+/// Computes the Spearman rank correlation between the visibility rankings of `reference` and
+/// `result`. Objects missing in one of the results are treated as having zero visibility and tied
+/// values get their average rank.
+///
+/// Returns `None` if the correlation is undefined, i.e., if there are less than two objects or one
+/// of the rankings consists of ties only.
+///
+/// # Arguments
+/// * `reference` - The reference visibility, e.g., from a ground truth setup.
+/// * `result` - The visibility result to evaluate.
+pub fn rank_correlation(reference: &Visibility, result: &Visibility) -> Option<f32> {
+    let (expected, actual) = paired_values(reference, result);
+    if expected.len() < 2 {
+        return None;
+    }
+
+    pearson_correlation(&ranks(&expected), &ranks(&actual)).map(|r| r as f32)
+}
+
+/// Computes the root mean square error of the per-object visibility of `result` compared to
+/// `reference`. Objects missing in one of the results are treated as having zero visibility.
+/// Returns zero if both results are empty.
+///
+/// # Arguments
+/// * `reference` - The reference visibility, e.g., from a ground truth setup.
+/// * `result` - The visibility result to evaluate.
+pub fn coverage_rmse(reference: &Visibility, result: &Visibility) -> f32 {
+    let (expected, actual) = paired_values(reference, result);
+    if expected.is_empty() {
+        return 0.0;
+    }
+
+    let squared_error: f64 = expected
+        .iter()
+        .zip(actual.iter())
+        .map(|(e, a)| (e - a) * (e - a))
+        .sum();
+
+    (squared_error / expected.len() as f64).sqrt() as f32
+}
+
+/// Returns the visibility values of both results for the union of their objects, ordered by
+/// object id. Missing objects get zero visibility.
+fn paired_values(reference: &Visibility, result: &Visibility) -> (Vec<f64>, Vec<f64>) {
+    let expected: HashMap<u32, f32> = reference.iter_sorted().collect();
+    let actual: HashMap<u32, f32> = result.iter_sorted().collect();
+    let object_ids: BTreeSet<u32> = expected.keys().chain(actual.keys()).copied().collect();
+
+    object_ids
+        .iter()
+        .map(|object_id| {
+            let e = expected.get(object_id).copied().unwrap_or(0.0) as f64;
+            let a = actual.get(object_id).copied().unwrap_or(0.0) as f64;
+            (e, a)
+        })
+        .unzip()
+}
+
+/// Returns the rank of each value, where tied values get their average rank.
+fn ranks(values: &[f64]) -> Vec<f64> {
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    order.sort_by(|a, b| values[*a].total_cmp(&values[*b]));
+
+    let mut result = vec![0.0; values.len()];
+    let mut start = 0;
+    while start < order.len() {
+        let mut end = start + 1;
+        while end < order.len() && values[order[end]] == values[order[start]] {
+            end += 1;
+        }
+
+        let rank = (start + end - 1) as f64 / 2.0;
+        for index in &order[start..end] {
+            result[*index] = rank;
+        }
+
+        start = end;
+    }
+
+    result
+}
+
+/// Returns the Pearson correlation of the given samples or `None` if one of them has no variance.
+fn pearson_correlation(x: &[f64], y: &[f64]) -> Option<f64> {
+    let n = x.len() as f64;
+    let mean_x = x.iter().sum::<f64>() / n;
+    let mean_y = y.iter().sum::<f64>() / n;
+
+    let mut covariance = 0.0;
+    let mut variance_x = 0.0;
+    let mut variance_y = 0.0;
+    for (a, b) in x.iter().zip(y.iter()) {
+        covariance += (a - mean_x) * (b - mean_y);
+        variance_x += (a - mean_x) * (a - mean_x);
+        variance_y += (b - mean_y) * (b - mean_y);
+    }
+
+    if variance_x == 0.0 || variance_y == 0.0 {
+        return None;
+    }
+
+    Some(covariance / (variance_x * variance_y).sqrt())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reference() -> Visibility {
+        Visibility::new(vec![(0, 0.4), (1, 0.3), (2, 0.2), (3, 0.0)])
+    }
+
+    #[test]
+    fn test_set_metrics() {
+        let result = Visibility::new(vec![(0, 0.5), (2, 0.1), (3, 0.1)]);
+        let metrics = set_metrics(&reference(), &result, 0.0);
+        assert!((metrics.precision - 2.0 / 3.0).abs() < 1e-6);
+        assert!((metrics.recall - 2.0 / 3.0).abs() < 1e-6);
+        assert!((metrics.f1 - 2.0 / 3.0).abs() < 1e-6);
+
+        let metrics = set_metrics(&reference(), &reference(), 0.0);
+        assert_eq!(metrics.f1, 1.0);
+
+        let empty = Visibility::default();
+        let metrics = set_metrics(&reference(), &empty, 0.0);
+        assert_eq!(metrics.precision, 1.0);
+        assert_eq!(metrics.recall, 0.0);
+        assert_eq!(metrics.f1, 0.0);
+    }
+
+    #[test]
+    fn test_rank_correlation() {
+        assert_eq!(rank_correlation(&reference(), &reference()), Some(1.0));
+
+        let reversed = Visibility::new(vec![(0, 0.0), (1, 0.2), (2, 0.3), (3, 0.4)]);
+        assert_eq!(rank_correlation(&reference(), &reversed), Some(-1.0));
+
+        let constant = Visibility::new(vec![(0, 0.1), (1, 0.1), (2, 0.1), (3, 0.1)]);
+        assert_eq!(rank_correlation(&reference(), &constant), None);
+        assert_eq!(rank_correlation(&Visibility::default(), &constant), None);
+    }
+
+    #[test]
+    fn test_ranks_with_ties() {
+        assert_eq!(ranks(&[0.5, 0.1, 0.5, 0.0]), vec![2.5, 1.0, 2.5, 0.0]);
+    }
+
+    #[test]
+    fn test_coverage_rmse() {
+        assert_eq!(coverage_rmse(&reference(), &reference()), 0.0);
+        assert_eq!(
+            coverage_rmse(&Visibility::default(), &Visibility::default()),
+            0.0
+        );
+
+        // object 1 is missing and counts as zero visibility
+        let result = Visibility::new(vec![(0, 0.4), (2, 0.2), (3, 0.0)]);
+        let rmse = coverage_rmse(&reference(), &result);
+        assert!((rmse - 0.15).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,249 @@
+use std::io::{Error, ErrorKind, Read, Result, Write};
+
+use crate::Visibility;
+
+/// The magic bytes at the start of a binary visibility report.
+const MAGIC: &[u8; 4] = b"OCCV";
+
+/// The version of the binary visibility report format.
+const VERSION: u32 = 1;
+
+/// The visibility results of a sequence of views, e.g., of one test setup.
+///
+/// The binary format is little-endian and consists of the magic bytes `OCCV`, the format version
+/// and the number of views as `u32`. Each view stores the total number of pixels (zero if unknown)
+/// and the number of entries as `u32`, followed by the entries. If the number of pixels is known,
+/// each entry consists of object id and visible pixel count (`u32`), from which the visibility is
+/// computed on reading. Otherwise, each entry consists of object id (`u32`) and visibility
+/// (`f32`).
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct VisibilityReport {
+    views: Vec<Visibility>,
+}
+
+impl VisibilityReport {
+    /// Creates a new report from the given per-view visibility results.
+    ///
+    /// # Arguments
+    /// * `views` - The visibility results in the order of the views.
+    pub fn new(views: Vec<Visibility>) -> Self {
+        Self { views }
+    }
+
+    /// Appends the visibility result of the next view.
+    ///
+    /// # Arguments
+    /// * `visibility` - The visibility result of the view.
+    pub fn push(&mut self, visibility: Visibility) {
+        self.views.push(visibility);
+    }
+
+    /// Returns the visibility results of all views.
+    #[inline]
+    pub fn views(&self) -> &[Visibility] {
+        &self.views
+    }
+
+    /// Returns the number of views in the report.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.views.len()
+    }
+
+    /// Returns true if the report contains no views.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.views.is_empty()
+    }
+
+    /// Writes the report in the binary format to the given writer.
+    ///
+    /// # Arguments
+    /// * `w` - The writer to write the report to.
+    pub fn write<W: Write>(&self, mut w: W) -> Result<()> {
+        w.write_all(MAGIC)?;
+        write_u32(&mut w, VERSION)?;
+        write_u32(&mut w, to_u32(self.views.len())?)?;
+
+        for visibility in self.views.iter() {
+            write_u32(&mut w, visibility.num_pixels().unwrap_or(0))?;
+            write_u32(&mut w, to_u32(visibility.len())?)?;
+
+            let pixel_counts = visibility.pixel_counts();
+            for (index, (object_id, value)) in visibility.iter_sorted().enumerate() {
+                write_u32(&mut w, object_id)?;
+                match pixel_counts {
+                    Some(pixel_counts) => write_u32(&mut w, pixel_counts[index])?,
+                    None => w.write_all(&value.to_le_bytes())?,
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Reads a report in the binary format from the given reader. Views with invalid entries,
+    /// e.g., duplicate objects or pixel counts exceeding the frame size, are rejected as invalid
+    /// data.
+    ///
+    /// # Arguments
+    /// * `r` - The reader to read the report from.
+    pub fn read<R: Read>(mut r: R) -> Result<Self> {
+        let mut magic = [0u8; 4];
+        r.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "Not a visibility report",
+            ));
+        }
+
+        let version = read_u32(&mut r)?;
+        if version != VERSION {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("Unsupported visibility report version {}", version),
+            ));
+        }
+
+        // the counts are not trusted for preallocation, as the data may be corrupted
+        let num_views = read_u32(&mut r)?;
+        let mut views = Vec::new();
+        for _ in 0..num_views {
+            let num_pixels = read_u32(&mut r)?;
+            let num_entries = read_u32(&mut r)?;
+
+            let visibility = if num_pixels > 0 {
+                let mut pixel_counts = Vec::new();
+                for _ in 0..num_entries {
+                    let object_id = read_u32(&mut r)?;
+                    pixel_counts.push((object_id, read_u32(&mut r)?));
+                }
+
+                Visibility::try_from_pixel_counts(pixel_counts, num_pixels)
+            } else {
+                let mut entries = Vec::new();
+                for _ in 0..num_entries {
+                    let object_id = read_u32(&mut r)?;
+                    entries.push((object_id, read_f32(&mut r)?));
+                }
+
+                Visibility::try_new(entries)
+            };
+
+            views.push(visibility.map_err(|err| Error::new(ErrorKind::InvalidData, err))?);
+        }
+
+        Ok(Self { views })
+    }
+}
+
+fn to_u32(value: usize) -> Result<u32> {
+    u32::try_from(value).map_err(|_| Error::new(ErrorKind::InvalidInput, "Too many entries"))
+}
+
+fn write_u32<W: Write>(w: &mut W, value: u32) -> Result<()> {
+    w.write_all(&value.to_le_bytes())
+}
+
+fn read_u32<R: Read>(r: &mut R) -> Result<u32> {
+    let mut buf = [0u8; 4];
+    r.read_exact(&mut buf)?;
+    Ok(u32::from_le_bytes(buf))
+}
+
+fn read_f32<R: Read>(r: &mut R) -> Result<f32> {
+    let mut buf = [0u8; 4];
+    r.read_exact(&mut buf)?;
+    Ok(f32::from_le_bytes(buf))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> VisibilityReport {
+        VisibilityReport::new(vec![
+            Visibility::new(vec![(2, 0.25), (0, 0.5)]),
+            Visibility::default(),
+            Visibility::from_pixel_counts([(4, 3), (1, 1)], 16),
+        ])
+    }
+
+    #[test]
+    fn test_write_read() {
+        let report = example();
+
+        let mut data = Vec::new();
+        report.write(&mut data).unwrap();
+        assert_eq!(&data[..4], MAGIC);
+
+        // header, two views with two and zero (id, visibility) entries and one view with two
+        // (id, pixel count) entries
+        assert_eq!(data.len(), 12 + (8 + 2 * 8) + 8 + (8 + 2 * 8));
+
+        let loaded = VisibilityReport::read(data.as_slice()).unwrap();
+        assert_eq!(loaded, report);
+        assert_eq!(loaded.views()[2].pixel_count(4), Some(3));
+    }
+
+    #[test]
+    fn test_read_invalid() {
+        assert!(VisibilityReport::read(b"ABCD".as_slice()).is_err());
+
+        let mut data = Vec::new();
+        example().write(&mut data).unwrap();
+        data.truncate(data.len() - 1);
+        assert!(VisibilityReport::read(data.as_slice()).is_err());
+
+        let mut data = Vec::new();
+        example().write(&mut data).unwrap();
+        data[4] = 2;
+        assert!(VisibilityReport::read(data.as_slice()).is_err());
+
+        // first entry of the first view has its visibility replaced by NaN
+        let mut data = Vec::new();
+        example().write(&mut data).unwrap();
+        data[24..28].copy_from_slice(&f32::NAN.to_le_bytes());
+        assert_invalid_data(&data);
+
+        // second object of the first view has the same id as the first one
+        let mut data = Vec::new();
+        example().write(&mut data).unwrap();
+        data[28..32].copy_from_slice(&0u32.to_le_bytes());
+        assert_invalid_data(&data);
+
+        // first pixel count of the last view exceeds its 16 pixels
+        let mut data = Vec::new();
+        example().write(&mut data).unwrap();
+        data[56..60].copy_from_slice(&17u32.to_le_bytes());
+        assert_invalid_data(&data);
+
+        // second object of the last view has the same id as the first one
+        let mut data = Vec::new();
+        example().write(&mut data).unwrap();
+        data[60..64].copy_from_slice(&4u32.to_le_bytes());
+        assert_invalid_data(&data);
+    }
+
+    fn assert_invalid_data(data: &[u8]) {
+        let err = VisibilityReport::read(data).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let report = example();
+        let json = serde_json::to_string(&report).unwrap();
+        assert_eq!(
+            serde_json::from_str::<VisibilityReport>(&json).unwrap(),
+            report
+        );
+    }
+}
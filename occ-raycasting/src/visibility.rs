@@ -0,0 +1,485 @@
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::num::NonZeroU32;
+
+use crate::Error;
+
+/// The visibility of the objects in a scene for a single view.
+///
+/// Each entry is a pair of object id and visibility, where the visibility is the fraction of the
+/// rendered image covered by the object. Every object occurs at most once and every visibility is
+/// within [0, 1]. The entries are kept sorted in descending order of
+/// visibility; entries with equal visibility are ordered by ascending object id.
+///
+/// If the result has been created from pixel counts, the raw number of visible pixels per object
+/// and the total number of pixels of the frame are available as well. In this case, entries with
+/// equal visibility are ordered by descending pixel count first, as for very large frames
+/// different pixel counts can round to the same visibility.
+///
+/// Two results are only equal if both their entries and their pixel counts are equal, i.e., a
+/// result created from pixel counts never equals one created from visibility values only.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "VisibilityRepr", into = "VisibilityRepr")
+)]
+pub struct Visibility {
+    entries: Vec<(u32, f32)>,
+
+    /// The total number of pixels of the frame and the number of visible pixels for each entry,
+    /// if known.
+    pixels: Option<(NonZeroU32, Vec<u32>)>,
+}
+
+impl Visibility {
+    /// Creates a new visibility result from the given (object id, visibility) pairs.
+    /// The pairs don't need to be sorted.
+    ///
+    /// # Arguments
+    /// * `entries` - The (object id, visibility) pairs.
+    ///
+    /// # Panics
+    /// Panics if any visibility is not within [0, 1] or an object occurs more than once.
+    /// Use [`Visibility::try_new`] for a fallible variant.
+    pub fn new(entries: Vec<(u32, f32)>) -> Self {
+        Self::try_new(entries).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Creates a new visibility result from the given (object id, visibility) pairs.
+    /// The pairs don't need to be sorted. Returns an error if any visibility is not within [0, 1]
+    /// or an object occurs more than once.
+    ///
+    /// # Arguments
+    /// * `entries` - The (object id, visibility) pairs.
+    pub fn try_new(mut entries: Vec<(u32, f32)>) -> Result<Self, Error> {
+        for (object_id, visibility) in entries.iter() {
+            if !visibility.is_finite() {
+                return Err(Error::NonFiniteVisibility(*object_id));
+            }
+
+            if !(0.0..=1.0).contains(visibility) {
+                return Err(Error::VisibilityOutOfRange(*object_id, *visibility));
+            }
+        }
+
+        check_unique_objects(entries.iter().map(|(object_id, _)| *object_id))?;
+
+        entries.sort_by(compare_entries);
+        Ok(Self {
+            entries,
+            pixels: None,
+        })
+    }
+
+    /// Creates a new visibility result from the number of visible pixels per object.
+    /// The visibility of each object is its pixel count divided by the total number of pixels.
+    /// Entries with equal visibility are ordered by descending pixel count and ascending object id.
+    ///
+    /// # Arguments
+    /// * `pixel_counts` - The (object id, visible pixels) pairs.
+    /// * `num_pixels` - The total number of pixels of the frame.
+    ///
+    /// # Panics
+    /// Panics if `num_pixels` is zero, any pixel count exceeds `num_pixels` or an object occurs
+    /// more than once. Use [`Visibility::try_from_pixel_counts`] for a fallible variant.
+    pub fn from_pixel_counts<I>(pixel_counts: I, num_pixels: u32) -> Self
+    where
+        I: IntoIterator<Item = (u32, u32)>,
+    {
+        Self::try_from_pixel_counts(pixel_counts, num_pixels)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Creates a new visibility result from the number of visible pixels per object.
+    /// Returns an error if `num_pixels` is zero, any pixel count exceeds `num_pixels` or an object
+    /// occurs more than once. See [`Visibility::from_pixel_counts`] for details.
+    ///
+    /// # Arguments
+    /// * `pixel_counts` - The (object id, visible pixels) pairs.
+    /// * `num_pixels` - The total number of pixels of the frame.
+    pub fn try_from_pixel_counts<I>(pixel_counts: I, num_pixels: u32) -> Result<Self, Error>
+    where
+        I: IntoIterator<Item = (u32, u32)>,
+    {
+        let num_pixels = NonZeroU32::new(num_pixels).ok_or(Error::NoPixels)?;
+        let pixel_counts: Vec<(u32, u32)> = pixel_counts.into_iter().collect();
+
+        if let Some((object_id, count)) = pixel_counts
+            .iter()
+            .find(|(_, count)| *count > num_pixels.get())
+        {
+            return Err(Error::PixelCountOutOfRange(*object_id, *count));
+        }
+
+        check_unique_objects(pixel_counts.iter().map(|(object_id, _)| *object_id))?;
+
+        let mut counted: Vec<((u32, f32), u32)> = pixel_counts
+            .into_iter()
+            .map(|(object_id, count)| {
+                let visibility = (count as f64 / num_pixels.get() as f64) as f32;
+                ((object_id, visibility), count)
+            })
+            .collect();
+        counted.sort_by(|(a, count_a), (b, count_b)| {
+            b.1.total_cmp(&a.1)
+                .then(count_b.cmp(count_a))
+                .then(a.0.cmp(&b.0))
+        });
+
+        let (entries, pixel_counts) = counted.into_iter().unzip();
+        Ok(Self {
+            entries,
+            pixels: Some((num_pixels, pixel_counts)),
+        })
+    }
+
+    /// Returns the number of objects in the result.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if the result contains no objects.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the visibility of the given object or `None` if the object is not part of the
+    /// result.
+    ///
+    /// # Arguments
+    /// * `object_id` - The id of the object to look up.
+    pub fn get(&self, object_id: u32) -> Option<f32> {
+        self.entries
+            .iter()
+            .find(|(id, _)| *id == object_id)
+            .map(|(_, visibility)| *visibility)
+    }
+
+    /// Returns the total number of pixels of the frame or `None` if the result has not been created
+    /// from pixel counts.
+    pub fn num_pixels(&self) -> Option<u32> {
+        self.pixels.as_ref().map(|(num_pixels, _)| num_pixels.get())
+    }
+
+    /// Returns the number of visible pixels of the given object or `None` if the object is not part
+    /// of the result or the result has not been created from pixel counts.
+    ///
+    /// # Arguments
+    /// * `object_id` - The id of the object to look up.
+    pub fn pixel_count(&self, object_id: u32) -> Option<u32> {
+        let (_, pixel_counts) = self.pixels.as_ref()?;
+
+        self.entries
+            .iter()
+            .position(|(id, _)| *id == object_id)
+            .map(|index| pixel_counts[index])
+    }
+
+    /// Returns the visible pixel counts in the order of the entries or `None` if the result has
+    /// not been created from pixel counts.
+    pub(crate) fn pixel_counts(&self) -> Option<&[u32]> {
+        self.pixels
+            .as_ref()
+            .map(|(_, pixel_counts)| pixel_counts.as_slice())
+    }
+
+    /// Returns all entries with at least the given number of visible pixels or `None` if the result
+    /// has not been created from pixel counts.
+    ///
+    /// # Arguments
+    /// * `min_pixels` - The minimal number of visible pixels.
+    pub fn with_min_pixels(&self, min_pixels: u32) -> Option<&[(u32, f32)]> {
+        let (_, pixel_counts) = self.pixels.as_ref()?;
+
+        let n = pixel_counts.partition_point(|count| *count >= min_pixels);
+        Some(&self.entries[..n])
+    }
+
+    /// Returns all entries whose visibility is strictly greater than the given threshold.
+    ///
+    /// # Arguments
+    /// * `threshold` - The visibility threshold.
+    pub fn visible_above(&self, threshold: f32) -> &[(u32, f32)] {
+        let n = self
+            .entries
+            .partition_point(|(_, visibility)| *visibility > threshold);
+        &self.entries[..n]
+    }
+
+    /// Returns the `n` most visible entries. If the result has less than `n` entries, all entries
+    /// are returned.
+    ///
+    /// # Arguments
+    /// * `n` - The maximal number of entries to return.
+    pub fn top_n(&self, n: usize) -> &[(u32, f32)] {
+        &self.entries[..n.min(self.entries.len())]
+    }
+
+    /// Returns an iterator over the entries in descending order of visibility.
+    pub fn iter_sorted(&self) -> impl Iterator<Item = (u32, f32)> + '_ {
+        self.entries.iter().copied()
+    }
+
+    /// Returns the sorted entries as slice.
+    #[inline]
+    pub fn as_slice(&self) -> &[(u32, f32)] {
+        &self.entries
+    }
+}
+
+impl TryFrom<Vec<(u32, f32)>> for Visibility {
+    type Error = Error;
+
+    fn try_from(entries: Vec<(u32, f32)>) -> Result<Self, Self::Error> {
+        Self::try_new(entries)
+    }
+}
+
+impl From<Visibility> for Vec<(u32, f32)> {
+    fn from(visibility: Visibility) -> Self {
+        visibility.entries
+    }
+}
+
+/// The serialized form of the visibility result. Results without pixel counts are stored as plain
+/// list of (object id, visibility) pairs.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(untagged)]
+enum VisibilityRepr {
+    Fractions(Vec<(u32, f32)>),
+    PixelCounts {
+        num_pixels: u32,
+        pixel_counts: Vec<(u32, u32)>,
+    },
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<VisibilityRepr> for Visibility {
+    type Error = Error;
+
+    fn try_from(repr: VisibilityRepr) -> Result<Self, Self::Error> {
+        match repr {
+            VisibilityRepr::Fractions(entries) => Self::try_new(entries),
+            VisibilityRepr::PixelCounts {
+                num_pixels,
+                pixel_counts,
+            } => Self::try_from_pixel_counts(pixel_counts, num_pixels),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<Visibility> for VisibilityRepr {
+    fn from(visibility: Visibility) -> Self {
+        let Some((num_pixels, pixel_counts)) = visibility.pixels else {
+            return Self::Fractions(visibility.entries);
+        };
+
+        let pixel_counts = visibility
+            .entries
+            .iter()
+            .zip(pixel_counts.iter())
+            .map(|((object_id, _), count)| (*object_id, *count))
+            .collect();
+
+        Self::PixelCounts {
+            num_pixels: num_pixels.get(),
+            pixel_counts,
+        }
+    }
+}
+
+/// Returns an error for the first object id that occurs more than once.
+fn check_unique_objects<I: Iterator<Item = u32>>(object_ids: I) -> Result<(), Error> {
+    let mut seen = HashSet::new();
+    for object_id in object_ids {
+        if !seen.insert(object_id) {
+            return Err(Error::DuplicateObject(object_id));
+        }
+    }
+
+    Ok(())
+}
+
+/// Orders entries by descending visibility and ascending object id.
+fn compare_entries(a: &(u32, f32), b: &(u32, f32)) -> Ordering {
+    b.1.total_cmp(&a.1).then(a.0.cmp(&b.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> Visibility {
+        Visibility::new(vec![(3, 0.1), (0, 0.5), (7, 0.0), (2, 0.25), (1, 0.1)])
+    }
+
+    #[test]
+    fn test_sorted() {
+        let visibility = example();
+        let entries: Vec<(u32, f32)> = visibility.iter_sorted().collect();
+        assert_eq!(
+            entries,
+            vec![(0, 0.5), (2, 0.25), (1, 0.1), (3, 0.1), (7, 0.0)]
+        );
+    }
+
+    #[test]
+    fn test_get() {
+        let visibility = example();
+        assert_eq!(visibility.get(2), Some(0.25));
+        assert_eq!(visibility.get(7), Some(0.0));
+        assert_eq!(visibility.get(42), None);
+    }
+
+    #[test]
+    fn test_visible_above() {
+        let visibility = example();
+        assert_eq!(visibility.visible_above(0.1), &[(0, 0.5), (2, 0.25)]);
+        assert_eq!(visibility.visible_above(0.0).len(), 4);
+        assert!(visibility.visible_above(1.0).is_empty());
+    }
+
+    #[test]
+    fn test_top_n() {
+        let visibility = example();
+        assert_eq!(visibility.top_n(2), &[(0, 0.5), (2, 0.25)]);
+        assert_eq!(visibility.top_n(10).len(), 5);
+        assert!(visibility.top_n(0).is_empty());
+    }
+
+    #[test]
+    fn test_vec_conversion() {
+        let visibility = Visibility::try_from(vec![(1, 0.2), (4, 0.6)]).unwrap();
+        assert_eq!(visibility.len(), 2);
+
+        let entries: Vec<(u32, f32)> = visibility.into();
+        assert_eq!(entries, vec![(4, 0.6), (1, 0.2)]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_non_finite() {
+        Visibility::new(vec![(0, f32::NAN), (1, 0.5)]);
+    }
+
+    #[test]
+    fn test_invalid_entries() {
+        assert_eq!(
+            Visibility::try_new(vec![(0, f32::NAN)]),
+            Err(Error::NonFiniteVisibility(0))
+        );
+        assert_eq!(
+            Visibility::try_new(vec![(1, 0.5), (2, 1.5)]),
+            Err(Error::VisibilityOutOfRange(2, 1.5))
+        );
+        assert_eq!(
+            Visibility::try_new(vec![(3, -0.1)]),
+            Err(Error::VisibilityOutOfRange(3, -0.1))
+        );
+        assert_eq!(
+            Visibility::try_new(vec![(0, 0.5), (0, 0.3)]),
+            Err(Error::DuplicateObject(0))
+        );
+        assert!(Visibility::try_from(vec![(0, 0.0), (1, 1.0)]).is_ok());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_duplicate_pixel_counts() {
+        Visibility::from_pixel_counts([(0, 1), (0, 2)], 4);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_pixel_count_exceeds_frame() {
+        Visibility::from_pixel_counts([(0, 10)], 4);
+    }
+
+    #[test]
+    fn test_invalid_pixel_counts() {
+        assert_eq!(
+            Visibility::try_from_pixel_counts([(0, 1)], 0),
+            Err(Error::NoPixels)
+        );
+        assert_eq!(
+            Visibility::try_from_pixel_counts([(1, 4), (0, 10)], 4),
+            Err(Error::PixelCountOutOfRange(0, 10))
+        );
+        assert_eq!(
+            Visibility::try_from_pixel_counts([(0, 1), (0, 2)], 4),
+            Err(Error::DuplicateObject(0))
+        );
+
+        let full = Visibility::try_from_pixel_counts([(0, 4)], 4).unwrap();
+        assert_eq!(full.get(0), Some(1.0));
+    }
+
+    #[test]
+    fn test_pixel_counts() {
+        let visibility = Visibility::from_pixel_counts([(5, 10), (2, 40), (9, 0), (1, 10)], 100);
+        assert_eq!(
+            visibility.as_slice(),
+            &[(2, 0.4), (1, 0.1), (5, 0.1), (9, 0.0)]
+        );
+        assert_eq!(visibility.num_pixels(), Some(100));
+        assert_eq!(visibility.pixel_count(5), Some(10));
+        assert_eq!(visibility.pixel_count(9), Some(0));
+        assert_eq!(visibility.pixel_count(3), None);
+        assert_eq!(visibility.with_min_pixels(10).unwrap().len(), 3);
+        assert_eq!(visibility.with_min_pixels(11).unwrap(), &[(2, 0.4)]);
+
+        let visibility = example();
+        assert_eq!(visibility.num_pixels(), None);
+        assert_eq!(visibility.pixel_count(0), None);
+        assert_eq!(visibility.with_min_pixels(1), None);
+
+        // equality takes the pixel counts into account
+        let counted = Visibility::from_pixel_counts([(0, 1)], 2);
+        assert_ne!(counted, Visibility::new(vec![(0, 0.5)]));
+        assert_eq!(
+            counted.as_slice(),
+            Visibility::new(vec![(0, 0.5)]).as_slice()
+        );
+    }
+
+    #[test]
+    fn test_pixel_counts_large_frame() {
+        // for an 8K frame, both pixel counts round to the same visibility
+        let num_pixels = 7680 * 4320;
+        let visibility = Visibility::from_pixel_counts([(0, 20000001), (1, 20000002)], num_pixels);
+        assert_eq!(visibility.get(0), visibility.get(1));
+
+        assert_eq!(visibility.as_slice()[0].0, 1);
+        assert_eq!(visibility.with_min_pixels(20000002).unwrap().len(), 1);
+        assert_eq!(visibility.with_min_pixels(20000001).unwrap().len(), 2);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let visibility = example();
+        let json = serde_json::to_string(&visibility).unwrap();
+        assert_eq!(json, "[[0,0.5],[2,0.25],[1,0.1],[3,0.1],[7,0.0]]");
+
+        let unsorted: Visibility = serde_json::from_str("[[1,0.1],[0,0.5]]").unwrap();
+        assert_eq!(unsorted.as_slice(), &[(0, 0.5), (1, 0.1)]);
+
+        let counted = Visibility::from_pixel_counts([(3, 1), (4, 3)], 4);
+        let json = serde_json::to_string(&counted).unwrap();
+        assert_eq!(json, r#"{"num_pixels":4,"pixel_counts":[[4,3],[3,1]]}"#);
+        assert_eq!(serde_json::from_str::<Visibility>(&json).unwrap(), counted);
+
+        assert!(serde_json::from_str::<Visibility>("[[0,1e99]]").is_err());
+        assert!(serde_json::from_str::<Visibility>("[[0,0.5],[0,0.3]]").is_err());
+        let duplicates = r#"{"num_pixels":4,"pixel_counts":[[0,1],[0,2]]}"#;
+        assert!(serde_json::from_str::<Visibility>(duplicates).is_err());
+
+        let invalid = r#"{"num_pixels":0,"pixel_counts":[]}"#;
+        assert!(serde_json::from_str::<Visibility>(invalid).is_err());
+    }
+}